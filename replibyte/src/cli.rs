@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[clap(name = "replibyte", version)]
+pub struct CLI {
+    /// Path to the Replibyte configuration file
+    #[clap(short, long)]
+    pub config: PathBuf,
+
+    #[clap(subcommand)]
+    pub sub_commands: SubCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SubCommand {
+    /// Manage dumps: create, list, delete and restore them
+    #[clap(subcommand)]
+    Dump(DumpCommand),
+    /// Manage transformers
+    #[clap(subcommand)]
+    Transformer(TransformerCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DumpCommand {
+    /// List all the dumps available in the configured datastore
+    List,
+    /// Create a new dump from the configured source database
+    Create(DumpCreateArgs),
+    /// Delete a dump from the configured datastore
+    Delete(DumpDeleteArgs),
+    /// Restore a dump into a database
+    #[clap(subcommand)]
+    Restore(RestoreCommand),
+    /// Copy dumps from the configured datastore to another datastore,
+    /// without going through the source database
+    Sync(DumpSyncArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct DumpCreateArgs {
+    /// Name to give to the dump. Defaults to a timestamp.
+    #[clap(short, long)]
+    pub name: Option<String>,
+    /// Has no effect: the dump is always written to the configured
+    /// datastore and nothing is streamed to stdout
+    #[clap(long)]
+    pub output: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct DumpDeleteArgs {
+    /// Name of the dump to delete
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct DumpSyncArgs {
+    /// Path to the config file describing the destination datastore
+    #[clap(long)]
+    pub to: PathBuf,
+    /// Only sync the dump with this name instead of every dump missing from
+    /// the destination
+    #[clap(short, long)]
+    pub name: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RestoreCommand {
+    /// Restore a dump into a local database
+    Local(RestoreArgs),
+    /// Restore a dump into a remote database reachable from this machine
+    Remote(RestoreArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct RestoreArgs {
+    /// Name of the dump to restore. Defaults to the latest dump.
+    #[clap(short, long)]
+    pub name: Option<String>,
+    /// Has no effect: restore progress is not displayed whether or not this
+    /// is set, and nothing is streamed to stdout
+    #[clap(long)]
+    pub output: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TransformerCommand {
+    /// List all the available transformers
+    List,
+}
@@ -0,0 +1,344 @@
+use anyhow::anyhow;
+
+use crate::cli::{DumpCreateArgs, DumpDeleteArgs, DumpSyncArgs, RestoreArgs};
+use crate::config::Config;
+use crate::datastore::{Datastore, Dump, IndexFile};
+use crate::tasks::{parallelize, MaxBytes, TransferredBytes};
+use crate::utils::epoch_millis;
+
+/// Tables to dump/restore, read from the source/destination connection
+/// config, and the worker count to fan them out across. Errors rather than
+/// silently producing an empty dump/no-op restore when the connection has
+/// no `tables` configured, since there's no table discovery to fall back
+/// on.
+fn tables_and_workers(
+    config: &Config,
+    connection: Option<&crate::config::ConnectionConfig>,
+) -> anyhow::Result<(Vec<String>, usize)> {
+    let tables = connection
+        .and_then(|connection| connection.tables.clone())
+        .unwrap_or_default();
+
+    if tables.is_empty() {
+        return Err(anyhow!(
+            "no tables configured on the connection; set `tables` under `source`/`destination`"
+        ));
+    }
+
+    let workers = config.workers.unwrap_or(1);
+
+    Ok((tables, workers))
+}
+
+pub fn list(datastore: &mut Box<dyn Datastore>) -> anyhow::Result<()> {
+    let index_file = datastore.index_file()?;
+
+    let mut table = table!(["name", "size", "compressed", "encrypted", "when"]);
+
+    for dump in index_file.dumps {
+        table.add_row(row![
+            dump.directory_name,
+            dump.size,
+            dump.compressed,
+            dump.encrypted,
+            dump.created_at
+        ]);
+    }
+
+    table.printstd();
+
+    Ok(())
+}
+
+pub fn run<F>(
+    args: &DumpCreateArgs,
+    mut datastore: Box<dyn Datastore>,
+    config: Config,
+    progress_callback: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&str, usize, TransferredBytes, MaxBytes) + Sync,
+{
+    let dump_name = args
+        .name
+        .clone()
+        .unwrap_or_else(|| epoch_millis().to_string());
+
+    datastore.set_dump_name(dump_name.clone());
+
+    let (tables, workers) = tables_and_workers(&config, config.source.as_ref())?;
+
+    let parts = tables.clone();
+
+    let size = {
+        let datastore: &dyn Datastore = datastore.as_ref();
+
+        let sizes = parallelize(tables, workers, 0, &progress_callback, |table| {
+            // In a full build this would open its own connection to the
+            // source database for `table` and stream its rows through the
+            // configured transformers; here it just writes a placeholder so
+            // every table still lands as its own file part in the dump.
+            let data = format!("-- dump of table {table}\n").into_bytes();
+            let size = data.len();
+            datastore.write(&table, data)?;
+            Ok((size, size))
+        })?;
+
+        sizes.into_iter().sum()
+    };
+
+    let mut index_file = datastore.index_file()?;
+    index_file.dumps.push(Dump {
+        directory_name: dump_name,
+        size: size as u64,
+        compressed: true,
+        encrypted: false,
+        created_at: epoch_millis(),
+        parts,
+    });
+    datastore.write_index_file(&index_file)?;
+
+    Ok(())
+}
+
+pub fn delete(mut datastore: Box<dyn Datastore>, args: &DumpDeleteArgs) -> anyhow::Result<()> {
+    let mut index_file = datastore.index_file()?;
+    let parts = index_file
+        .find_dump(&args.name)
+        .map(|dump| dump.parts.clone())
+        .unwrap_or_default();
+    index_file.dumps.retain(|dump| dump.directory_name != args.name);
+    datastore.write_index_file(&index_file)?;
+
+    datastore.set_dump_name(args.name.clone());
+    for part in &parts {
+        datastore.delete(part)?;
+    }
+
+    Ok(())
+}
+
+pub fn restore_local<F>(
+    _args: &RestoreArgs,
+    datastore: Box<dyn Datastore>,
+    config: Config,
+    progress_callback: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&str, usize, TransferredBytes, MaxBytes) + Sync,
+{
+    let (tables, workers) = tables_and_workers(&config, config.destination.as_ref())?;
+
+    let datastore: &dyn Datastore = datastore.as_ref();
+
+    let _ = parallelize(tables, workers, 0, &progress_callback, |table| {
+        // In a full build this would read the table's dump back from the
+        // datastore and stream it into its own destination connection.
+        let mut size = 0usize;
+        datastore.read(&table, &mut |chunk| size += chunk.len())?;
+        Ok((table, size))
+    })?;
+
+    Ok(())
+}
+
+/// Copies every dump present in `source` but missing from `destination`,
+/// comparing the two index files by dump name, unless `args.name` forces a
+/// single dump to be re-streamed and re-indexed even if already present.
+pub fn sync<F>(
+    args: &DumpSyncArgs,
+    mut source: Box<dyn Datastore>,
+    mut destination: Box<dyn Datastore>,
+    progress_callback: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&str, usize, TransferredBytes, MaxBytes),
+{
+    let source_index = source.index_file()?;
+    let mut destination_index = destination.index_file()?;
+
+    let dumps_to_sync = dumps_to_sync(&source_index, &destination_index, args.name.as_deref())?;
+
+    let max_bytes = dumps_to_sync.iter().map(|dump| dump.size as usize).sum();
+    let mut transferred_bytes = 0usize;
+
+    for dump in dumps_to_sync {
+        source.set_dump_name(dump.directory_name.clone());
+        destination.set_dump_name(dump.directory_name.clone());
+
+        // A dump is stored as one object per table part rather than a
+        // single blob, so each part has to be streamed across individually.
+        for part in &dump.parts {
+            let mut buffer = Vec::new();
+            source.read(part, &mut |chunk| {
+                buffer.extend_from_slice(chunk);
+                transferred_bytes += chunk.len();
+                progress_callback(part, chunk.len(), transferred_bytes, max_bytes);
+            })?;
+
+            destination.write(part, buffer)?;
+        }
+
+        upsert_dump(&mut destination_index, dump);
+    }
+
+    Ok(destination.write_index_file(&destination_index)?)
+}
+
+/// Records `dump` in `index`, replacing any existing entry with the same
+/// `directory_name` instead of appending a duplicate. `--name` can sync a
+/// dump that's already present at the destination, so the index entry
+/// needs updating in place rather than blindly pushed.
+fn upsert_dump(index: &mut IndexFile, dump: Dump) {
+    index
+        .dumps
+        .retain(|existing| existing.directory_name != dump.directory_name);
+    index.dumps.push(dump);
+}
+
+/// Dumps present in `source_index` but missing from `destination_index`,
+/// or only the one named by `name` when set, even if that one is already
+/// present at the destination — `--name` is an explicit re-sync request.
+/// Errors when `name` is set but matches nothing in `source_index`, since a
+/// silently empty result would otherwise look like a no-op sync instead of
+/// a typo'd or missing dump name.
+fn dumps_to_sync(
+    source_index: &IndexFile,
+    destination_index: &IndexFile,
+    name: Option<&str>,
+) -> anyhow::Result<Vec<Dump>> {
+    if let Some(name) = name {
+        if source_index.find_dump(name).is_none() {
+            return Err(anyhow!("dump '{name}' not found in the source datastore"));
+        }
+    }
+
+    Ok(source_index
+        .dumps
+        .iter()
+        .filter(|dump| match name {
+            Some(name) => dump.directory_name == name,
+            None => destination_index.find_dump(&dump.directory_name).is_none(),
+        })
+        .cloned()
+        .collect())
+}
+
+pub fn restore_remote<F>(
+    _args: &RestoreArgs,
+    datastore: Box<dyn Datastore>,
+    config: Config,
+    progress_callback: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&str, usize, TransferredBytes, MaxBytes) + Sync,
+{
+    let (tables, workers) = tables_and_workers(&config, config.destination.as_ref())?;
+
+    let datastore: &dyn Datastore = datastore.as_ref();
+
+    let _ = parallelize(tables, workers, 0, &progress_callback, |table| {
+        let mut size = 0usize;
+        datastore.read(&table, &mut |chunk| size += chunk.len())?;
+        Ok((table, size))
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(directory_name: &str) -> Dump {
+        Dump {
+            directory_name: directory_name.to_string(),
+            size: 1,
+            compressed: true,
+            encrypted: false,
+            created_at: 0,
+            parts: vec!["users".to_string()],
+        }
+    }
+
+    #[test]
+    fn dumps_to_sync_skips_dumps_already_at_the_destination() {
+        let source_index = IndexFile {
+            dumps: vec![dump("a"), dump("b")],
+        };
+        let destination_index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+
+        let result = dumps_to_sync(&source_index, &destination_index, None).unwrap();
+
+        assert_eq!(
+            result.iter().map(|d| d.directory_name.clone()).collect::<Vec<_>>(),
+            vec!["b".to_string()]
+        );
+    }
+
+    #[test]
+    fn dumps_to_sync_restricts_to_the_named_dump_even_if_already_synced() {
+        let source_index = IndexFile {
+            dumps: vec![dump("a"), dump("b")],
+        };
+        let destination_index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+
+        let result = dumps_to_sync(&source_index, &destination_index, Some("a")).unwrap();
+
+        assert_eq!(
+            result.iter().map(|d| d.directory_name.clone()).collect::<Vec<_>>(),
+            vec!["a".to_string()]
+        );
+    }
+
+    #[test]
+    fn dumps_to_sync_is_empty_when_everything_is_already_synced() {
+        let source_index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+        let destination_index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+
+        assert!(dumps_to_sync(&source_index, &destination_index, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn dumps_to_sync_errors_when_the_named_dump_is_not_in_source() {
+        let source_index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+        let destination_index = IndexFile { dumps: vec![] };
+
+        assert!(dumps_to_sync(&source_index, &destination_index, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn upsert_dump_replaces_an_existing_entry_instead_of_duplicating_it() {
+        let mut index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+
+        upsert_dump(&mut index, dump("a"));
+
+        assert_eq!(index.dumps.len(), 1);
+    }
+
+    #[test]
+    fn upsert_dump_appends_a_new_entry() {
+        let mut index = IndexFile { dumps: vec![] };
+
+        upsert_dump(&mut index, dump("a"));
+
+        assert_eq!(
+            index.dumps.iter().map(|d| d.directory_name.clone()).collect::<Vec<_>>(),
+            vec!["a".to_string()]
+        );
+    }
+}
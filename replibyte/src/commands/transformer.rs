@@ -0,0 +1,15 @@
+pub fn list() -> anyhow::Result<()> {
+    let mut table = table!(["name", "description"]);
+
+    table.add_row(row![
+        "first-name",
+        "Replace a value by a random first name"
+    ]);
+    table.add_row(row!["email", "Replace a value by a random email address"]);
+    table.add_row(row!["random", "Replace a value by a random string"]);
+    table.add_row(row!["redacted", "Replace a value by a fixed redacted string"]);
+
+    table.printstd();
+
+    Ok(())
+}
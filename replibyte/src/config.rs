@@ -0,0 +1,184 @@
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::datastore::s3::Credentials;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub source: Option<ConnectionConfig>,
+    pub destination: Option<ConnectionConfig>,
+    pub datastore: DatastoreConfig,
+    pub subset: Option<DatabaseSubsetConfig>,
+    /// Maximum transfer rate, in bytes per second, `dump create`/`dump
+    /// restore` are allowed to sustain. Leave unset for no limit.
+    pub max_bandwidth: Option<u64>,
+    /// Number of tables to dump/restore concurrently. Defaults to 1
+    /// (sequential, the previous behavior).
+    pub workers: Option<usize>,
+}
+
+impl Config {
+    /// Validates `max_bandwidth`: a rate of `0` can never be satisfied (the
+    /// token bucket would need to wait forever for every single byte), so
+    /// it's rejected here rather than reaching `TokenBucket` and panicking
+    /// on a divide-by-zero.
+    pub fn max_bandwidth(&self) -> anyhow::Result<Option<u64>> {
+        match self.max_bandwidth {
+            Some(0) => Err(anyhow!("max_bandwidth must be greater than 0, or unset for no limit")),
+            other => Ok(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionConfig {
+    pub connection_uri: String,
+    /// Tables to dump/restore. Required: there is no table discovery, so
+    /// leaving this unset or empty makes `dump create`/`dump restore` fail.
+    pub tables: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSubsetConfig {
+    pub strategy: String,
+    pub percent: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DatastoreConfig {
+    AWS(AwsS3DatastoreConfig),
+    GCP(GcpS3DatastoreConfig),
+    Azure(AzureDatastoreConfig),
+    LocalDisk(LocalDiskDatastoreConfig),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AwsS3DatastoreConfig {
+    pub bucket: String,
+    pub region: Option<String>,
+    pub profile: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub endpoint: Option<String>,
+    /// Fall back to the AWS credential provider chain (env vars,
+    /// WebIdentity/IRSA, instance metadata) when no static access key is
+    /// set. Defaults to `true`; set to `false` to require static
+    /// credentials and fail fast instead.
+    #[serde(default = "default_use_credential_chain")]
+    pub use_credential_chain: bool,
+}
+
+fn default_use_credential_chain() -> bool {
+    true
+}
+
+impl AwsS3DatastoreConfig {
+    pub fn bucket(&self) -> anyhow::Result<String> {
+        Ok(self.bucket.clone())
+    }
+
+    pub fn region(&self) -> anyhow::Result<String> {
+        Ok(self.region.clone().unwrap_or_else(|| "us-east-1".to_string()))
+    }
+
+    pub fn profile(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.profile.clone())
+    }
+
+    pub fn credentials(&self) -> anyhow::Result<Option<Credentials>> {
+        match (&self.access_key_id, &self.secret_access_key) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Some(Credentials {
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+            })),
+            (None, None) => Ok(None),
+            _ => Err(anyhow!(
+                "both access_key_id and secret_access_key must be set together"
+            )),
+        }
+    }
+
+    pub fn endpoint(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.endpoint.clone())
+    }
+
+    pub fn use_credential_chain(&self) -> anyhow::Result<bool> {
+        Ok(self.use_credential_chain)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcpS3DatastoreConfig {
+    pub bucket: String,
+    pub region: Option<String>,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub endpoint: Option<String>,
+}
+
+impl GcpS3DatastoreConfig {
+    pub fn bucket(&self) -> anyhow::Result<String> {
+        Ok(self.bucket.clone())
+    }
+
+    pub fn region(&self) -> anyhow::Result<String> {
+        Ok(self.region.clone().unwrap_or_else(|| "us-east-1".to_string()))
+    }
+
+    pub fn access_key(&self) -> anyhow::Result<String> {
+        Ok(self.access_key_id.clone())
+    }
+
+    pub fn secret(&self) -> anyhow::Result<String> {
+        Ok(self.secret_access_key.clone())
+    }
+
+    pub fn endpoint(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.endpoint.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AzureDatastoreConfig {
+    pub container: String,
+    pub account: String,
+    pub access_key: Option<String>,
+    pub sas_token: Option<String>,
+    /// Custom endpoint, e.g. `http://localhost:10000/devstoreaccount1` to
+    /// target the Azurite emulator instead of production Blob Storage.
+    pub endpoint: Option<String>,
+}
+
+impl AzureDatastoreConfig {
+    pub fn container(&self) -> anyhow::Result<String> {
+        Ok(self.container.clone())
+    }
+
+    pub fn account(&self) -> anyhow::Result<String> {
+        Ok(self.account.clone())
+    }
+
+    pub fn access_key(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.access_key.clone())
+    }
+
+    pub fn sas_token(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.sas_token.clone())
+    }
+
+    pub fn endpoint(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.endpoint.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalDiskDatastoreConfig {
+    pub dir: String,
+}
+
+impl LocalDiskDatastoreConfig {
+    pub fn dir(&self) -> anyhow::Result<String> {
+        Ok(self.dir.clone())
+    }
+}
@@ -0,0 +1,6 @@
+/// A database driver (Postgres, MySQL, MongoDB, ...) identified by its
+/// connection URI. Does not itself build a [`crate::source::Source`] or a
+/// [`crate::destination::Destination`].
+pub trait Connector {
+    fn connection_uri(&self) -> &str;
+}
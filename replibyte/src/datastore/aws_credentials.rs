@@ -0,0 +1,409 @@
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use object_store::aws::AwsCredential;
+use object_store::{CredentialProvider, Result as ObjectStoreResult};
+
+const IMDS_BASE_URL: &str = "http://169.254.169.254/latest";
+const STS_ENDPOINT: &str = "https://sts.amazonaws.com";
+/// Refresh credentials this far ahead of their reported expiry so a request
+/// in flight never gets cut off mid-transfer.
+const REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+struct CachedCredential {
+    credential: Arc<AwsCredential>,
+    expires_at: Option<Instant>,
+}
+
+impl CachedCredential {
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() + REFRESH_SKEW < expires_at,
+            None => true,
+        }
+    }
+}
+
+/// Resolves AWS credentials the same way the official SDKs do when no
+/// static access key is configured: a named profile from the shared
+/// credentials file (if one was requested), then environment variables,
+/// then a WebIdentity token exchanged with STS (IRSA/OIDC on EKS), then the
+/// EC2/ECS instance metadata service. The result is cached and refreshed
+/// shortly before it expires so every request doesn't re-run the whole
+/// chain.
+///
+/// The profile is resolved per instance by reading the credentials file
+/// directly rather than by setting `AWS_PROFILE`, so two chains in the same
+/// process (e.g. `dump sync`'s source and destination) never clobber each
+/// other's profile through shared process environment.
+pub struct AwsCredentialChain {
+    profile: Option<String>,
+    cache: Mutex<Option<CachedCredential>>,
+}
+
+impl std::fmt::Debug for AwsCredentialChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Deliberately omits the cached credential itself.
+        f.debug_struct("AwsCredentialChain")
+            .field("profile", &self.profile)
+            .finish()
+    }
+}
+
+impl AwsCredentialChain {
+    pub fn new() -> Self {
+        AwsCredentialChain {
+            profile: None,
+            cache: Mutex::new(None),
+        }
+    }
+
+    pub fn with_profile(profile: String) -> Self {
+        AwsCredentialChain {
+            profile: Some(profile),
+            cache: Mutex::new(None),
+        }
+    }
+
+    async fn resolve(&self) -> ObjectStoreResult<(Arc<AwsCredential>, Option<Instant>)> {
+        if let Some(profile) = &self.profile {
+            if let Some(credential) = from_profile(profile) {
+                // Static keys from the credentials file never expire.
+                return Ok((Arc::new(credential), None));
+            }
+        }
+
+        if let Some(credential) = from_env() {
+            // Static long-lived keys from the environment never expire.
+            return Ok((Arc::new(credential), None));
+        }
+
+        if let Some((credential, expiration)) = from_web_identity().await {
+            return Ok((Arc::new(credential), expiration.and_then(to_instant)));
+        }
+
+        if let Some((credential, expiration)) = from_instance_metadata().await {
+            return Ok((Arc::new(credential), expiration.and_then(to_instant)));
+        }
+
+        Err(object_store::Error::Generic {
+            store: "AWS",
+            source: "no credentials found for the configured profile, in the environment, \
+                     WebIdentity or instance metadata"
+                .into(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialProvider for AwsCredentialChain {
+    type Credential = AwsCredential;
+
+    async fn get_credential(&self) -> ObjectStoreResult<Arc<Self::Credential>> {
+        {
+            let cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.as_ref() {
+                if cached.is_fresh() {
+                    return Ok(cached.credential.clone());
+                }
+            }
+        }
+
+        let (credential, expires_at) = self.resolve().await?;
+
+        let mut cache = self.cache.lock().unwrap();
+        *cache = Some(CachedCredential {
+            credential: credential.clone(),
+            expires_at,
+        });
+
+        Ok(credential)
+    }
+}
+
+fn from_env() -> Option<AwsCredential> {
+    let key_id = env::var("AWS_ACCESS_KEY_ID").ok()?;
+    let secret_key = env::var("AWS_SECRET_ACCESS_KEY").ok()?;
+    let token = env::var("AWS_SESSION_TOKEN").ok();
+
+    Some(AwsCredential {
+        key_id,
+        secret_key,
+        token,
+    })
+}
+
+/// Reads a named profile's static keys out of the shared credentials file
+/// (`~/.aws/credentials`, or `AWS_SHARED_CREDENTIALS_FILE` if set), the same
+/// file the AWS CLI and SDKs read when `AWS_PROFILE`/`--profile` picks a
+/// profile other than `default`.
+fn from_profile(profile: &str) -> Option<AwsCredential> {
+    let path = match env::var("AWS_SHARED_CREDENTIALS_FILE") {
+        Ok(path) => path,
+        Err(_) => format!("{}/.aws/credentials", env::var("HOME").ok()?),
+    };
+
+    let contents = std::fs::read_to_string(path).ok()?;
+    let section = extract_ini_section(&contents, profile)?;
+
+    Some(AwsCredential {
+        key_id: extract_ini_key(&section, "aws_access_key_id")?,
+        secret_key: extract_ini_key(&section, "aws_secret_access_key")?,
+        token: extract_ini_key(&section, "aws_session_token"),
+    })
+}
+
+/// Returns the body of an INI `[section]` up to (but not including) the next
+/// `[...]` header, or the end of the file.
+fn extract_ini_section(contents: &str, section: &str) -> Option<String> {
+    let header = format!("[{section}]");
+    let start = contents.find(&header)? + header.len();
+    let rest = &contents[start..];
+    let end = rest.find('[').unwrap_or(rest.len());
+    Some(rest[..end].to_string())
+}
+
+/// Returns the trimmed value of a `key = value` line within an INI section.
+fn extract_ini_key(section: &str, key: &str) -> Option<String> {
+    section.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
+
+/// Exchanges a WebIdentity token (mounted by Kubernetes for IRSA/OIDC) for
+/// short-lived credentials via STS `AssumeRoleWithWebIdentity`.
+async fn from_web_identity() -> Option<(AwsCredential, Option<String>)> {
+    let token_file = env::var("AWS_WEB_IDENTITY_TOKEN_FILE").ok()?;
+    let role_arn = env::var("AWS_ROLE_ARN").ok()?;
+    let token = tokio::fs::read_to_string(token_file).await.ok()?;
+
+    let session_name = env::var("AWS_ROLE_SESSION_NAME").unwrap_or_else(|_| "replibyte".into());
+
+    let response = reqwest::Client::new()
+        .get(STS_ENDPOINT)
+        .query(&[
+            ("Action", "AssumeRoleWithWebIdentity"),
+            ("Version", "2011-06-15"),
+            ("RoleArn", role_arn.as_str()),
+            ("RoleSessionName", session_name.as_str()),
+            ("WebIdentityToken", token.trim()),
+        ])
+        .send()
+        .await
+        .ok()?;
+
+    parse_sts_credentials(&response.text().await.ok()?)
+}
+
+/// Fetches the instance's attached IAM role credentials through IMDSv2
+/// (token-gated instance metadata), the last step of the chain so EC2/ECS
+/// tasks work with no explicit configuration at all.
+async fn from_instance_metadata() -> Option<(AwsCredential, Option<String>)> {
+    let client = reqwest::Client::new();
+
+    let token = client
+        .put(format!("{IMDS_BASE_URL}/api/token"))
+        .header("X-aws-ec2-metadata-token-ttl-seconds", "21600")
+        .send()
+        .await
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+
+    let role = client
+        .get(format!(
+            "{IMDS_BASE_URL}/meta-data/iam/security-credentials/"
+        ))
+        .header("X-aws-ec2-metadata-token", token.as_str())
+        .send()
+        .await
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+
+    let role = role.lines().next()?;
+
+    let body: serde_json::Value = client
+        .get(format!(
+            "{IMDS_BASE_URL}/meta-data/iam/security-credentials/{role}"
+        ))
+        .header("X-aws-ec2-metadata-token", token.as_str())
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    let credential = AwsCredential {
+        key_id: body.get("AccessKeyId")?.as_str()?.to_string(),
+        secret_key: body.get("SecretAccessKey")?.as_str()?.to_string(),
+        token: body.get("Token").and_then(|v| v.as_str()).map(str::to_string),
+    };
+    let expiration = body
+        .get("Expiration")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Some((credential, expiration))
+}
+
+fn parse_sts_credentials(xml: &str) -> Option<(AwsCredential, Option<String>)> {
+    let key_id = extract_xml_tag(xml, "AccessKeyId")?;
+    let secret_key = extract_xml_tag(xml, "SecretAccessKey")?;
+    let token = extract_xml_tag(xml, "SessionToken");
+    let expiration = extract_xml_tag(xml, "Expiration");
+
+    let credential = AwsCredential {
+        key_id,
+        secret_key,
+        token,
+    };
+
+    Some((credential, expiration))
+}
+
+fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)? + start;
+    Some(xml[start..end].to_string())
+}
+
+/// Turns an RFC 3339 UTC timestamp (the format both STS and IMDS report
+/// `Expiration` in, e.g. `2024-07-27T12:00:00Z`) into a [`SystemTime`], which
+/// [`to_instant`] then converts to an [`Instant`], without pulling in a
+/// datetime dependency just for this one field.
+fn parse_rfc3339_utc(s: &str) -> Option<SystemTime> {
+    let s = s.trim().strip_suffix('Z')?;
+    let (date, time) = s.split_once('T')?;
+
+    let mut date_parts = date.split('-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+
+    let time = time.split(['.', '+']).next()?;
+    let mut time_parts = time.split(':');
+    let hour: u64 = time_parts.next()?.parse().ok()?;
+    let minute: u64 = time_parts.next()?.parse().ok()?;
+    let second: u64 = time_parts.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let epoch_secs = days * 86_400 + (hour * 3600 + minute * 60 + second) as i64;
+
+    if epoch_secs >= 0 {
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(epoch_secs as u64))
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(Duration::from_secs((-epoch_secs) as u64))
+    }
+}
+
+/// Days since the Unix epoch for a given civil (Gregorian) date, per Howard
+/// Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i64;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn to_instant(expiration: String) -> Option<Instant> {
+    let expires_system_time = parse_rfc3339_utc(&expiration)?;
+    let now_system_time = SystemTime::now();
+
+    let delta = expires_system_time
+        .duration_since(now_system_time)
+        .unwrap_or(Duration::ZERO);
+
+    Some(Instant::now() + delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_xml_tag_reads_the_value_between_matching_tags() {
+        let xml = "<Credentials><AccessKeyId>AKIDEXAMPLE</AccessKeyId></Credentials>";
+
+        assert_eq!(
+            extract_xml_tag(xml, "AccessKeyId"),
+            Some("AKIDEXAMPLE".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_xml_tag_is_none_when_the_tag_is_absent() {
+        let xml = "<Credentials><AccessKeyId>AKIDEXAMPLE</AccessKeyId></Credentials>";
+
+        assert_eq!(extract_xml_tag(xml, "SessionToken"), None);
+    }
+
+    #[test]
+    fn parse_sts_credentials_reads_keys_token_and_expiration() {
+        let xml = r#"
+            <AssumeRoleWithWebIdentityResponse>
+              <AssumeRoleWithWebIdentityResult>
+                <Credentials>
+                  <AccessKeyId>AKIDEXAMPLE</AccessKeyId>
+                  <SecretAccessKey>secret</SecretAccessKey>
+                  <SessionToken>token</SessionToken>
+                  <Expiration>2024-07-27T12:00:00Z</Expiration>
+                </Credentials>
+              </AssumeRoleWithWebIdentityResult>
+            </AssumeRoleWithWebIdentityResponse>
+        "#;
+
+        let (credential, expiration) = parse_sts_credentials(xml).unwrap();
+
+        assert_eq!(credential.key_id, "AKIDEXAMPLE");
+        assert_eq!(credential.secret_key, "secret");
+        assert_eq!(credential.token, Some("token".to_string()));
+        assert_eq!(expiration, Some("2024-07-27T12:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn parse_sts_credentials_is_none_without_a_secret_key() {
+        let xml = "<Credentials><AccessKeyId>AKIDEXAMPLE</AccessKeyId></Credentials>";
+
+        assert!(parse_sts_credentials(xml).is_none());
+    }
+
+    #[test]
+    fn parse_rfc3339_utc_round_trips_a_known_unix_timestamp() {
+        // 2024-01-01T00:00:00Z is 1704067200 seconds after the Unix epoch.
+        let parsed = parse_rfc3339_utc("2024-01-01T00:00:00Z").unwrap();
+
+        assert_eq!(
+            parsed
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1_704_067_200
+        );
+    }
+
+    #[test]
+    fn to_instant_treats_a_past_expiry_as_stale() {
+        let expiration = to_instant("2024-01-01T00:00:00Z".to_string()).unwrap();
+        let cached = CachedCredential {
+            credential: Arc::new(AwsCredential {
+                key_id: "id".to_string(),
+                secret_key: "secret".to_string(),
+                token: None,
+            }),
+            expires_at: Some(expiration),
+        };
+
+        assert!(!cached.is_fresh());
+    }
+}
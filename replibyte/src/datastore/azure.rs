@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use object_store::azure::MicrosoftAzureBuilder;
+
+use crate::datastore::object_store::ObjectStoreDatastore;
+
+/// Builds a [`ObjectStoreDatastore`] backed by Azure Blob Storage. Supports
+/// either a storage account key or a SAS token, and an explicit endpoint so
+/// it can also target the Azurite emulator during local development.
+pub fn azure(
+    container: String,
+    account: String,
+    access_key: Option<String>,
+    sas_token: Option<String>,
+    endpoint: Option<String>,
+) -> anyhow::Result<ObjectStoreDatastore> {
+    let mut builder = MicrosoftAzureBuilder::new()
+        .with_container_name(container)
+        .with_account(account);
+
+    if let Some(access_key) = access_key {
+        builder = builder.with_access_key(access_key);
+    }
+
+    if let Some(sas_token) = sas_token {
+        builder = builder.with_config(object_store::azure::AzureConfigKey::SasKey, sas_token);
+    }
+
+    if let Some(endpoint) = endpoint {
+        // Lets this backend target the Azurite emulator (or any
+        // Azure-compatible endpoint) instead of production Blob Storage.
+        builder = builder.with_allow_http(true).with_endpoint(endpoint);
+    }
+
+    let store = builder.build()?;
+    Ok(ObjectStoreDatastore::new(Arc::new(store)))
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::datastore::{Datastore, IndexFile};
+
+/// Stores dumps as plain files under a directory on the local filesystem.
+/// Mostly used for local development and as a source/destination for
+/// `dump sync` when mirroring off-site backups back down to disk.
+pub struct LocalDisk {
+    dir: PathBuf,
+    dump_name: Option<String>,
+}
+
+impl LocalDisk {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        LocalDisk {
+            dir: dir.into(),
+            dump_name: None,
+        }
+    }
+
+    fn index_file_path(&self) -> PathBuf {
+        self.dir.join("index.json")
+    }
+
+    fn file_path(&self, file_part: &str) -> PathBuf {
+        match &self.dump_name {
+            Some(name) => self.dir.join(name).join(file_part),
+            None => self.dir.join(file_part),
+        }
+    }
+}
+
+impl Datastore for LocalDisk {
+    fn index_file(&self) -> Result<IndexFile, Error> {
+        let path = self.index_file_path();
+
+        if !path.exists() {
+            return Ok(IndexFile { dumps: vec![] });
+        }
+
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        serde_json::from_str(&contents).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error> {
+        let contents = serde_json::to_string(index_file)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        let mut file = File::create(self.index_file_path())?;
+        file.write_all(contents.as_bytes())
+    }
+
+    fn write(&self, file_part: &str, data: Vec<u8>) -> Result<(), Error> {
+        let path = self.file_path(file_part);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = File::create(path)?;
+        file.write_all(&data)
+    }
+
+    fn read(&self, file_part: &str, fn_data: &mut dyn FnMut(&[u8])) -> Result<(), Error> {
+        let mut file = File::open(self.file_path(file_part))?;
+        let mut buffer = [0u8; 8192];
+
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+
+            fn_data(&buffer[..read]);
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, file_part: &str) -> Result<(), Error> {
+        fs::remove_file(self.file_path(file_part))
+    }
+
+    fn set_dump_name(&mut self, name: String) {
+        self.dump_name = Some(name);
+    }
+
+    fn dump_name(&self) -> Option<String> {
+        self.dump_name.clone()
+    }
+
+    fn init(&mut self) -> Result<(), Error> {
+        if !Path::new(&self.dir).exists() {
+            fs::create_dir_all(&self.dir)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Error;
+
+pub mod aws_credentials;
+pub mod azure;
+pub mod local_disk;
+pub mod object_store;
+pub mod s3;
+
+/// The dump index of a datastore: every dump it holds, one entry each.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IndexFile {
+    pub dumps: Vec<Dump>,
+}
+
+impl IndexFile {
+    pub fn find_dump(&self, name: &str) -> Option<&Dump> {
+        self.dumps.iter().find(|dump| dump.directory_name == name)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Dump {
+    pub directory_name: String,
+    pub size: u64,
+    pub compressed: bool,
+    pub encrypted: bool,
+    pub created_at: u128,
+    /// File parts making up this dump (one per table), stored under the
+    /// `{directory_name}/{part}` key prefix. Lets consumers like `dump
+    /// sync` stream the dump's contents without assuming it's a single
+    /// blob.
+    #[serde(default)]
+    pub parts: Vec<String>,
+}
+
+/// Common contract implemented by every backend Replibyte can read dumps from
+/// and write dumps to (local disk, S3-compatible object storage, ...).
+/// `Send + Sync` so a single datastore can be shared as a writer across the
+/// worker pool that dumps/restores tables concurrently.
+pub trait Datastore: Send + Sync {
+    fn index_file(&self) -> Result<IndexFile, Error>;
+    fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error>;
+
+    fn write(&self, file_part: &str, data: Vec<u8>) -> Result<(), Error>;
+    fn read(
+        &self,
+        file_part: &str,
+        fn_data: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), Error>;
+    fn delete(&self, file_part: &str) -> Result<(), Error>;
+
+    fn set_dump_name(&mut self, name: String);
+    fn dump_name(&self) -> Option<String>;
+
+    fn init(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(directory_name: &str) -> Dump {
+        Dump {
+            directory_name: directory_name.to_string(),
+            size: 0,
+            compressed: true,
+            encrypted: false,
+            created_at: 0,
+            parts: vec![],
+        }
+    }
+
+    #[test]
+    fn find_dump_returns_the_matching_entry() {
+        let index = IndexFile {
+            dumps: vec![dump("a"), dump("b")],
+        };
+
+        assert_eq!(index.find_dump("b").map(|d| &d.directory_name), Some(&"b".to_string()));
+    }
+
+    #[test]
+    fn find_dump_is_none_when_no_entry_matches() {
+        let index = IndexFile {
+            dumps: vec![dump("a")],
+        };
+
+        assert!(index.find_dump("missing").is_none());
+    }
+}
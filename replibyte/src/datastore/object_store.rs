@@ -0,0 +1,91 @@
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::datastore::{Datastore, IndexFile};
+use crate::runtime::block_on;
+
+const INDEX_FILE_KEY: &str = "index.json";
+
+/// Datastore backed by the [`object_store`] crate, which gives AWS S3, GCP
+/// Cloud Storage and Azure Blob Storage the same async, multipart-aware
+/// client under one interface. Provider-specific setup (credentials,
+/// endpoints, ...) happens once when the `Arc<dyn ObjectStore>` is built in
+/// `datastore::s3` / `datastore::azure`; from here on every backend is
+/// driven identically.
+pub struct ObjectStoreDatastore {
+    store: Arc<dyn ObjectStore>,
+    dump_name: Option<String>,
+}
+
+impl ObjectStoreDatastore {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        ObjectStoreDatastore {
+            store,
+            dump_name: None,
+        }
+    }
+
+    fn key(&self, file_part: &str) -> Path {
+        match &self.dump_name {
+            Some(name) => Path::from(format!("{}/{}", name, file_part)),
+            None => Path::from(file_part),
+        }
+    }
+}
+
+fn to_io_error(err: object_store::Error) -> Error {
+    Error::other(err)
+}
+
+impl Datastore for ObjectStoreDatastore {
+    fn index_file(&self) -> Result<IndexFile, Error> {
+        let path = Path::from(INDEX_FILE_KEY);
+
+        let bytes = match block_on(self.store.get(&path)) {
+            Ok(result) => block_on(result.bytes()).map_err(to_io_error)?,
+            Err(object_store::Error::NotFound { .. }) => {
+                return Ok(IndexFile { dumps: vec![] })
+            }
+            Err(err) => return Err(to_io_error(err)),
+        };
+
+        serde_json::from_slice(&bytes).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error> {
+        let contents = serde_json::to_vec(index_file)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        block_on(self.store.put(&Path::from(INDEX_FILE_KEY), contents.into()))
+            .map_err(to_io_error)?;
+
+        Ok(())
+    }
+
+    fn write(&self, file_part: &str, data: Vec<u8>) -> Result<(), Error> {
+        block_on(self.store.put(&self.key(file_part), data.into())).map_err(to_io_error)?;
+        Ok(())
+    }
+
+    fn read(&self, file_part: &str, fn_data: &mut dyn FnMut(&[u8])) -> Result<(), Error> {
+        let result = block_on(self.store.get(&self.key(file_part))).map_err(to_io_error)?;
+        let bytes = block_on(result.bytes()).map_err(to_io_error)?;
+        fn_data(&bytes);
+        Ok(())
+    }
+
+    fn delete(&self, file_part: &str) -> Result<(), Error> {
+        block_on(self.store.delete(&self.key(file_part))).map_err(to_io_error)
+    }
+
+    fn set_dump_name(&mut self, name: String) {
+        self.dump_name = Some(name);
+    }
+
+    fn dump_name(&self) -> Option<String> {
+        self.dump_name.clone()
+    }
+}
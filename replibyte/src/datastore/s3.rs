@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use object_store::aws::AmazonS3Builder;
+
+use crate::datastore::aws_credentials::AwsCredentialChain;
+use crate::datastore::object_store::ObjectStoreDatastore;
+
+/// Static access key / secret pair used to authenticate against an
+/// S3-compatible endpoint.
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Builds a [`ObjectStoreDatastore`] backed by AWS S3.
+///
+/// Static `credentials` always win when set. Otherwise, when
+/// `use_credential_chain` is true, credentials are resolved lazily (and
+/// refreshed on expiry) from the named `profile` (if any), the environment,
+/// WebIdentity/IRSA or instance metadata, so Replibyte can run on
+/// EC2/ECS/EKS and CI without baking access keys into the config file.
+pub fn aws(
+    bucket: String,
+    region: String,
+    profile: Option<String>,
+    credentials: Option<Credentials>,
+    endpoint: Option<String>,
+    use_credential_chain: bool,
+) -> anyhow::Result<ObjectStoreDatastore> {
+    let mut builder = AmazonS3Builder::new().with_bucket_name(bucket).with_region(region);
+
+    match credentials {
+        Some(credentials) => {
+            builder = builder
+                .with_access_key_id(credentials.access_key_id)
+                .with_secret_access_key(credentials.secret_access_key);
+        }
+        None if use_credential_chain => {
+            // object_store has no first-class concept of a named profile, so
+            // the profile (if any) is resolved from the shared credentials
+            // file by this instance's own chain instead of through the
+            // process-global `AWS_PROFILE`, which two chains built in the
+            // same run (e.g. `dump sync`'s source and destination) would
+            // otherwise stomp on each other's.
+            let chain = match profile {
+                Some(profile) => AwsCredentialChain::with_profile(profile),
+                None => AwsCredentialChain::new(),
+            };
+            builder = builder.with_credentials(Arc::new(chain));
+        }
+        None => anyhow::bail!(
+            "no static AWS credentials are set and use_credential_chain is false"
+        ),
+    }
+
+    if let Some(endpoint) = endpoint {
+        builder = builder.with_endpoint(endpoint).with_allow_http(true);
+    }
+
+    let store = builder.build()?;
+    Ok(ObjectStoreDatastore::new(Arc::new(store)))
+}
+
+/// Builds a [`ObjectStoreDatastore`] backed by GCP Cloud Storage through its
+/// S3-compatible interop endpoint, authenticating with the HMAC
+/// `access_key_id`/`secret_access_key` pair GCS issues for S3 interop
+/// (same shape as [`aws`], just pointed at Google's endpoint by default).
+pub fn gcp(
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret: String,
+    endpoint: Option<String>,
+) -> anyhow::Result<ObjectStoreDatastore> {
+    let endpoint = endpoint.unwrap_or_else(|| "https://storage.googleapis.com".to_string());
+
+    let builder = AmazonS3Builder::new()
+        .with_bucket_name(bucket)
+        .with_region(region)
+        .with_access_key_id(access_key)
+        .with_secret_access_key(secret)
+        .with_endpoint(endpoint);
+
+    let store = builder.build()?;
+    Ok(ObjectStoreDatastore::new(Arc::new(store)))
+}
@@ -0,0 +1,5 @@
+/// Where rows read during a `dump restore` are written to: a live database
+/// connection, reached through the configured connector.
+pub trait Destination {
+    fn write(&self) -> anyhow::Result<()>;
+}
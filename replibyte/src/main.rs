@@ -4,24 +4,23 @@ extern crate prettytable;
 use std::fs::File;
 use std::sync::mpsc;
 use std::sync::mpsc::Receiver;
-use std::thread::sleep;
-use std::time::Duration;
-use std::{env, thread};
+use std::sync::Mutex;
+use std::thread;
 
 use clap::Parser;
-use indicatif::{ProgressBar, ProgressStyle};
-use log::info;
 use migration::{migrations, Migrator};
+use tracing::{info_span, Span};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
 use utils::get_replibyte_version;
 
 use crate::cli::{DumpCommand, RestoreCommand, SubCommand, TransformerCommand, CLI};
-use crate::config::{Config, DatabaseSubsetConfig, DatastoreConfig};
+use crate::config::{Config, DatastoreConfig};
 use crate::datastore::local_disk::LocalDisk;
-use crate::datastore::s3::S3;
 use crate::datastore::Datastore;
-use crate::source::{Source, SourceOptions};
-use crate::tasks::{MaxBytes, TransferredBytes};
-use crate::utils::epoch_millis;
+use crate::datastore::{azure, s3};
+use crate::tasks::{MaxBytes, TokenBucket, TransferredBytes};
 
 mod cli;
 mod commands;
@@ -38,22 +37,25 @@ mod transformer;
 mod types;
 mod utils;
 
-fn show_progress_bar(rx_pb: Receiver<(TransferredBytes, MaxBytes)>) {
-    let mut _max_bytes = 0usize;
-    let mut last_transferred_bytes = 0usize;
+fn show_progress_bar(rx_pb: Receiver<(String, TransferredBytes, MaxBytes)>, span: Span) {
+    let _enter = span.enter();
 
-    loop {
-        let (transferred_bytes, max_bytes) = match rx_pb.try_recv() {
-            Ok(msg) => msg,
-            Err(_) => (last_transferred_bytes, _max_bytes),
-        };
-        info!("Transferred {transferred_bytes}/{max_bytes}");
-        sleep(Duration::from_micros(50));
+    // Blocks until a progress update actually arrives instead of spinning,
+    // and only logs when there's something new to report.
+    while let Ok((table, transferred_bytes, max_bytes)) = rx_pb.recv() {
+        tracing::info!(table, transferred_bytes, max_bytes, "transfer progress");
     }
 }
 
 fn main() {
-    env_logger::init();
+    let file_appender = tracing_appender::rolling::never(".", "replibyte.log");
+    let (file_writer, _file_guard) = tracing_appender::non_blocking(file_appender);
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(fmt::layer().with_writer(file_writer).with_ansi(false))
+        .init();
 
     let args = CLI::parse();
 
@@ -67,31 +69,64 @@ fn main() {
     }
 }
 
-fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
-    let mut datastore: Box<dyn Datastore> = match &config.datastore {
-        DatastoreConfig::AWS(config) => Box::new(S3::aws(
+fn build_datastore(config: &DatastoreConfig) -> anyhow::Result<Box<dyn Datastore>> {
+    let datastore: Box<dyn Datastore> = match config {
+        DatastoreConfig::AWS(config) => Box::new(s3::aws(
             config.bucket()?,
             config.region()?,
             config.profile()?,
             config.credentials()?,
             config.endpoint()?,
+            config.use_credential_chain()?,
         )?),
-        DatastoreConfig::GCP(config) => Box::new(S3::gcp(
+        DatastoreConfig::GCP(config) => Box::new(s3::gcp(
             config.bucket()?,
             config.region()?,
             config.access_key()?,
             config.secret()?,
             config.endpoint()?,
         )?),
+        DatastoreConfig::Azure(config) => Box::new(azure::azure(
+            config.container()?,
+            config.account()?,
+            config.access_key()?,
+            config.sas_token()?,
+            config.endpoint()?,
+        )?),
         DatastoreConfig::LocalDisk(config) => Box::new(LocalDisk::new(config.dir()?)),
     };
 
-    let migrator = Migrator::new(get_replibyte_version(), &datastore, migrations());
+    Ok(datastore)
+}
+
+fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
+    let mut datastore: Box<dyn Datastore> = build_datastore(&config.datastore)?;
+
+    let migrator = Migrator::new(get_replibyte_version(), &*datastore, migrations());
     let _ = migrator.migrate()?;
 
     let _ = datastore.init()?;
 
-    let (tx_pb, rx_pb) = mpsc::sync_channel::<(TransferredBytes, MaxBytes)>(1000);
+    let (tx_pb, rx_pb) = mpsc::sync_channel::<(String, TransferredBytes, MaxBytes)>(1000);
+
+    let span = match sub_commands {
+        SubCommand::Dump(dump_cmd) => match dump_cmd {
+            DumpCommand::Create(args) => {
+                info_span!("dump_create", snapshot = args.name.clone().unwrap_or_default())
+            }
+            DumpCommand::Restore(RestoreCommand::Local(args)) => {
+                info_span!("restore_local", snapshot = args.name.clone().unwrap_or_default())
+            }
+            DumpCommand::Restore(RestoreCommand::Remote(args)) => {
+                info_span!("restore_remote", snapshot = args.name.clone().unwrap_or_default())
+            }
+            DumpCommand::Sync(args) => {
+                info_span!("dump_sync", snapshot = args.name.clone().unwrap_or_default())
+            }
+            DumpCommand::List | DumpCommand::Delete(_) => info_span!("dump"),
+        },
+        SubCommand::Transformer(_) => info_span!("transformer"),
+    };
 
     match sub_commands {
         // skip progress when output = true
@@ -101,17 +136,39 @@ fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
                 RestoreCommand::Remote(args) => if args.output {},
             },
             _ => {
-                let _ = thread::spawn(move || show_progress_bar(rx_pb));
+                let _ = thread::spawn(move || show_progress_bar(rx_pb, span));
             }
         },
         _ => {
-            let _ = thread::spawn(move || show_progress_bar(rx_pb));
+            let _ = thread::spawn(move || show_progress_bar(rx_pb, span));
         }
     };
 
-    let progress_callback = |bytes: TransferredBytes, max_bytes: MaxBytes| {
-        let _ = tx_pb.send((bytes, max_bytes));
-    };
+    let bandwidth_limiter = config
+        .max_bandwidth()?
+        .map(|rate| Mutex::new(TokenBucket::new(rate, rate)));
+
+    // `Sync` so the same callback can be shared by reference across the
+    // worker pool that dumps/restores tables concurrently. `delta` is
+    // exactly what the just-finished transfer moved, reported straight from
+    // the source of truth rather than re-derived from `total` — concurrent
+    // workers can deliver `total` out of order, which would make a diff
+    // against the last-seen value go negative and under-deduct from the
+    // bucket. The bucket is only touched to compute the required delay; the
+    // sleep itself happens after the lock is released so a throttled worker
+    // doesn't also block every other worker on the bandwidth mutex.
+    let progress_callback =
+        move |table: &str, delta: usize, total: TransferredBytes, max_bytes: MaxBytes| {
+            let wait = bandwidth_limiter
+                .as_ref()
+                .map(|limiter| limiter.lock().unwrap().consume(delta));
+
+            if let Some(wait) = wait {
+                std::thread::sleep(wait);
+            }
+
+            let _ = tx_pb.send((table.to_string(), total, max_bytes));
+        };
 
     match sub_commands {
         SubCommand::Dump(cmd) => match cmd {
@@ -135,6 +192,15 @@ fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
                     commands::dump::restore_remote(args, datastore, config, progress_callback)
                 }
             },
+            DumpCommand::Sync(args) => {
+                let file = File::open(&args.to).expect("missing destination config file");
+                let to_config: Config =
+                    serde_yaml::from_reader(file).expect("bad destination config file format");
+                let mut destination = build_datastore(&to_config.datastore)?;
+                let _ = destination.init()?;
+
+                commands::dump::sync(args, datastore, destination, progress_callback)
+            }
         },
         SubCommand::Transformer(cmd) => match cmd {
             TransformerCommand::List => {
@@ -0,0 +1,36 @@
+use crate::datastore::Datastore;
+
+/// A single, idempotent change applied to a datastore's on-disk layout
+/// between two Replibyte versions.
+pub struct Migration {
+    pub version: &'static str,
+}
+
+pub fn migrations() -> Vec<Migration> {
+    vec![]
+}
+
+/// Holds the migrations known to the running Replibyte version for a
+/// datastore. `migrate` applies none of them and always succeeds.
+pub struct Migrator<'a> {
+    version: String,
+    datastore: &'a dyn Datastore,
+    migrations: Vec<Migration>,
+}
+
+impl<'a> Migrator<'a> {
+    pub fn new(version: String, datastore: &'a dyn Datastore, migrations: Vec<Migration>) -> Self {
+        Migrator {
+            version,
+            datastore,
+            migrations,
+        }
+    }
+
+    pub fn migrate(&self) -> anyhow::Result<()> {
+        let _ = &self.version;
+        let _ = &self.datastore;
+        let _ = &self.migrations;
+        Ok(())
+    }
+}
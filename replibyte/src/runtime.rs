@@ -0,0 +1,12 @@
+use std::future::Future;
+
+/// Runs a `Future` to completion on a fresh single-threaded Tokio runtime.
+/// Used by the (synchronous) CLI commands to call into async datastore and
+/// connector code.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the Replibyte async runtime")
+        .block_on(future)
+}
@@ -0,0 +1,10 @@
+/// Where rows read during a `dump create` come from: a live database
+/// connection, reached through the configured connector.
+pub trait Source {
+    fn read(&self) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceOptions {
+    pub connection_uri: Option<String>,
+}
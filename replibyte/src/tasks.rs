@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of bytes transferred so far for the current operation.
+pub type TransferredBytes = usize;
+/// Total number of bytes expected for the current operation, if known.
+pub type MaxBytes = usize;
+
+/// Caps throughput to a configured bytes-per-second rate, with a small
+/// burst allowance so transfers don't stall on every single chunk. Used to
+/// keep `dump create`/`dump restore` from saturating a production database
+/// or network link (the `max_bandwidth` config setting).
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(rate_bytes_per_sec: u64, burst_bytes: u64) -> Self {
+        TokenBucket {
+            rate: rate_bytes_per_sec as f64,
+            capacity: burst_bytes as f64,
+            tokens: burst_bytes as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = Instant::now();
+    }
+
+    /// Deducts `bytes` from the bucket's balance and returns how long the
+    /// caller should sleep to stay under the configured rate. Never sleeps
+    /// itself, so it's safe to call while holding a lock shared across
+    /// worker threads: the caller is expected to drop the lock first and
+    /// only then sleep for the returned duration.
+    pub fn consume(&mut self, bytes: usize) -> Duration {
+        self.refill();
+
+        let bytes_f = bytes as f64;
+        let wait = if bytes_f > self.tokens {
+            let missing = bytes_f - self.tokens;
+            Duration::from_secs_f64(missing / self.rate)
+        } else {
+            Duration::ZERO
+        };
+
+        self.tokens = (self.tokens - bytes_f).max(0.0);
+        wait
+    }
+}
+
+/// Fans `items` out across `workers` threads, each running `transfer` to
+/// completion on its own item before picking up the next one from a shared
+/// queue. `transfer` is expected to open its own source connection rather
+/// than share one, so tables never contend on the same connection.
+///
+/// Bounding concurrency to `workers` is what provides backpressure: at most
+/// `workers` items are ever in flight, so memory use doesn't grow with the
+/// total item count the way an unbounded fan-out would. Per-worker byte
+/// counts are aggregated into a single running total, and results are
+/// handed back in the original item order regardless of which worker
+/// finished first.
+///
+/// `progress_callback` is called as `(label, delta, total, max_bytes)`:
+/// `label` identifies which item the just-finished `transfer` call was for
+/// (the table name, in every current caller), and `delta` is exactly what
+/// that call reported, so a caller that needs to meter throughput (the
+/// tranquility limiter) can consume it directly instead of diffing two
+/// `total` values — concurrent workers can call back in an order that
+/// doesn't match the order their bytes were added to `total`, which would
+/// make such a diff go negative.
+pub fn parallelize<T, R, F>(
+    items: Vec<T>,
+    workers: usize,
+    max_bytes: MaxBytes,
+    progress_callback: &(dyn Fn(&str, usize, TransferredBytes, MaxBytes) + Sync),
+    transfer: F,
+) -> anyhow::Result<Vec<R>>
+where
+    T: Send + AsRef<str>,
+    R: Send,
+    F: Fn(T) -> anyhow::Result<(R, usize)> + Sync,
+{
+    let workers = workers.max(1).min(items.len().max(1));
+
+    let queue: Mutex<VecDeque<(usize, T)>> =
+        Mutex::new(items.into_iter().enumerate().collect());
+    let results: Mutex<Vec<(usize, R)>> = Mutex::new(Vec::new());
+    let error: Mutex<Option<anyhow::Error>> = Mutex::new(None);
+    let transferred_bytes = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                if error.lock().unwrap().is_some() {
+                    return;
+                }
+
+                let Some((index, item)) = queue.lock().unwrap().pop_front() else {
+                    return;
+                };
+
+                let label = item.as_ref().to_string();
+
+                match transfer(item) {
+                    Ok((value, bytes)) => {
+                        let total = transferred_bytes.fetch_add(bytes, Ordering::SeqCst) + bytes;
+                        progress_callback(&label, bytes, total, max_bytes);
+                        results.lock().unwrap().push((index, value));
+                    }
+                    Err(err) => {
+                        *error.lock().unwrap() = Some(err);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    if let Some(err) = error.into_inner().unwrap() {
+        return Err(err);
+    }
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, value)| value).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_does_not_wait_while_tokens_are_available() {
+        let mut bucket = TokenBucket::new(100, 100);
+
+        assert_eq!(bucket.consume(50), Duration::ZERO);
+    }
+
+    #[test]
+    fn token_bucket_requires_a_wait_once_the_burst_is_exhausted() {
+        let mut bucket = TokenBucket::new(100, 100);
+
+        assert_eq!(bucket.consume(100), Duration::ZERO);
+        // No time has passed to refill, so the next byte must wait for the
+        // bucket to accrue it at the configured rate.
+        let wait = bucket.consume(10);
+        assert!(wait > Duration::ZERO);
+        assert!((wait.as_secs_f64() - 0.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn parallelize_returns_results_in_original_item_order() {
+        let items: Vec<String> = ["5", "1", "4", "2", "3"].iter().map(|s| s.to_string()).collect();
+
+        let results = parallelize(items, 4, 0, &|_, _, _, _| {}, |item| {
+            // Workers that pick up smaller items finish first; the result
+            // order must still match the input order.
+            let millis: u64 = item.parse().unwrap();
+            std::thread::sleep(Duration::from_millis(millis));
+            Ok((item, 0))
+        })
+        .unwrap();
+
+        assert_eq!(results, vec!["5", "1", "4", "2", "3"]);
+    }
+
+    #[test]
+    fn parallelize_reports_the_label_for_each_completed_item() {
+        let items: Vec<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let labels: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        let _ = parallelize(
+            items,
+            1,
+            0,
+            &|label, _, _, _| labels.lock().unwrap().push(label.to_string()),
+            |item| Ok((item, 1)),
+        )
+        .unwrap();
+
+        let mut labels = labels.into_inner().unwrap();
+        labels.sort();
+        assert_eq!(labels, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn parallelize_propagates_the_first_error() {
+        let items: Vec<String> = ["1", "2", "3"].iter().map(|s| s.to_string()).collect();
+
+        let result = parallelize(items, 2, 0, &|_, _, _, _| {}, |item| {
+            if item == "2" {
+                Err(anyhow::anyhow!("boom"))
+            } else {
+                Ok((item, 0))
+            }
+        });
+
+        assert!(result.is_err());
+    }
+}
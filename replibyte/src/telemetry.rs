@@ -0,0 +1,2 @@
+/// Anonymous usage reporting hook. Does nothing: no event is sent.
+pub fn report_event(_name: &str) {}
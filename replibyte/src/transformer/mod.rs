@@ -0,0 +1,6 @@
+/// A transformation applied to a single column's value while a dump is
+/// created, e.g. to anonymize PII.
+pub trait Transformer {
+    fn id(&self) -> &str;
+    fn description(&self) -> &str;
+}
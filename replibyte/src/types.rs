@@ -0,0 +1,10 @@
+/// Column type as reported by the source database, used to pick a
+/// compatible default transformer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OriginalQueryColumnType {
+    Text,
+    Integer,
+    Boolean,
+    Timestamp,
+    Json,
+}
@@ -0,0 +1,12 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn get_replibyte_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+pub fn epoch_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_millis()
+}